@@ -5,9 +5,64 @@ use std::ops::RangeInclusive;
 use chrono::prelude::Datelike;
 use chrono::{Duration, NaiveDate};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 // Reexport Weekday from chrono as part of the public type.
 pub use chrono::Weekday;
 
+// `chrono::Weekday` is a foreign type re-exported as part of our public
+// API, so it needs its own `with` adapters rather than a derive.
+
+#[cfg(feature = "serde")]
+mod weekday_serde {
+    use chrono::Weekday;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(wday: &Weekday, serializer: S) -> Result<S::Ok, S::Error> {
+        wday.to_string().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Weekday, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod weekday_range_serde {
+    use std::ops::RangeInclusive;
+
+    use chrono::Weekday;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Repr {
+        #[serde(with = "super::weekday_serde")]
+        start: Weekday,
+        #[serde(with = "super::weekday_serde")]
+        end: Weekday,
+    }
+
+    pub fn serialize<S: Serializer>(
+        range: &RangeInclusive<Weekday>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Repr {
+            start: *range.start(),
+            end: *range.end(),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RangeInclusive<Weekday>, D::Error> {
+        let repr = Repr::deserialize(deserializer)?;
+        Ok(repr.start..=repr.end)
+    }
+}
+
 // Display
 
 fn wday_str(wday: Weekday) -> &'static str {
@@ -49,6 +104,7 @@ pub struct InvalidMonth;
 
 // DaySelector
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Default)]
 pub struct DaySelector {
     pub year: Vec<YearRange>,
@@ -59,6 +115,7 @@ pub struct DaySelector {
 
 // YearRange
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct YearRange {
     pub range: RangeInclusive<u16>,
@@ -67,6 +124,7 @@ pub struct YearRange {
 
 // MonthdayRange
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub enum MonthdayRange {
     Month {
@@ -81,6 +139,7 @@ pub enum MonthdayRange {
 
 // Date
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Copy, Debug)]
 pub enum Date {
     Fixed {
@@ -125,6 +184,7 @@ impl Display for Date {
 
 // DateOffset
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Default)]
 pub struct DateOffset {
     pub wday_offset: WeekDayOffset,
@@ -162,11 +222,12 @@ impl Display for DateOffset {
 
 // WeekDayOffset
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Copy, Debug)]
 pub enum WeekDayOffset {
     None,
-    Next(Weekday),
-    Prev(Weekday),
+    Next(#[cfg_attr(feature = "serde", serde(with = "weekday_serde"))] Weekday),
+    Prev(#[cfg_attr(feature = "serde", serde(with = "weekday_serde"))] Weekday),
 }
 
 impl Default for WeekDayOffset {
@@ -190,9 +251,11 @@ impl Display for WeekDayOffset {
 
 // WeekDayRange
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub enum WeekDayRange {
     Fixed {
+        #[cfg_attr(feature = "serde", serde(with = "weekday_range_serde"))]
         range: RangeInclusive<Weekday>,
         offset: i64,
         nth: [bool; 5],
@@ -246,7 +309,8 @@ impl Display for WeekDayRange {
 
 // HolidayKind
 
-#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum HolidayKind {
     Public,
     School,
@@ -263,6 +327,7 @@ impl Display for HolidayKind {
 
 // WeekRange
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct WeekRange {
     pub range: RangeInclusive<u8>,
@@ -334,6 +399,24 @@ impl Display for Month {
     }
 }
 
+// Serialize as the integer discriminant rather than the variant name, so
+// a serialized `Month` is a plain `u8` on the wire.
+
+#[cfg(feature = "serde")]
+impl Serialize for Month {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Month {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+        Month::try_from(value).map_err(|_| serde::de::Error::custom("invalid month"))
+    }
+}
+
 macro_rules! impl_try_into_for_month {
     ( $from_type: ty ) => {
         impl TryFrom<$from_type> for Month {
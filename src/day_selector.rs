@@ -0,0 +1,289 @@
+use std::ops::RangeInclusive;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use opening_hours_syntax::rules::day::{
+    Date, DateOffset, DaySelector, HolidayKind, Month, MonthdayRange, WeekDayRange, WeekRange,
+    YearRange,
+};
+
+use crate::holidays::Context;
+
+// DateFilter
+//
+// Implemented by `DaySelector` and each of its fragments (`YearRange`,
+// `MonthdayRange`, `WeekRange`, `WeekDayRange`) so `RuleSequence::schedule_at`
+// can ask "does this date match?" without caring which kind of fragment is
+// doing the matching. `WeekDayRange::Holiday` is the one fragment that can't
+// answer on its own: it needs the `HolidayProvider` carried by `Context` to
+// resolve `PH`/`SH` against real dates.
+
+pub trait DateFilter {
+    fn filter(&self, date: NaiveDate) -> bool {
+        self.filter_with_context(date, &Context::default())
+    }
+
+    fn filter_with_context(&self, date: NaiveDate, context: &Context) -> bool;
+
+    /// If this selector matches `date` *through* a `PH`/`SH` weekday
+    /// entry and no plain (non-holiday) weekday entry also matches
+    /// `date`, the `HolidayKind` responsible for the
+    /// match. `None` otherwise — including when `date` is a holiday but
+    /// the selector would have matched regardless (e.g. `We,PH`, on a
+    /// Wednesday that's also a public holiday).
+    ///
+    /// Only `DaySelector` overrides this; its fragments have no notion
+    /// of "the rest of the selector", so the default is `None`.
+    fn matching_holiday_kind(&self, date: NaiveDate, context: &Context) -> Option<HolidayKind> {
+        let _ = (date, context);
+        None
+    }
+}
+
+impl DateFilter for DaySelector {
+    fn filter_with_context(&self, date: NaiveDate, context: &Context) -> bool {
+        match_any(&self.year, date, context)
+            && match_any(&self.monthday, date, context)
+            && match_any(&self.week, date, context)
+            && match_any(&self.weekday, date, context)
+    }
+
+    fn matching_holiday_kind(&self, date: NaiveDate, context: &Context) -> Option<HolidayKind> {
+        if !self.filter_with_context(date, context) {
+            return None;
+        }
+
+        let kind = self.weekday.iter().find_map(|w| match w {
+            WeekDayRange::Holiday { kind, offset } => {
+                let shifted = date - Duration::days(*offset);
+                let holidays = context.holidays()?;
+
+                let is_match = match kind {
+                    HolidayKind::Public => holidays.is_public_holiday(shifted),
+                    HolidayKind::School => holidays.is_school_holiday(shifted),
+                };
+
+                is_match.then_some(*kind)
+            }
+            WeekDayRange::Fixed { .. } => None,
+        })?;
+
+        // A non-holiday weekday entry matching the same date means the
+        // selector would have matched regardless of the holiday, so the
+        // match isn't attributable to the holiday entry.
+        let non_holiday_also_matches = self.weekday.iter().any(|w| match w {
+            WeekDayRange::Fixed { .. } => w.filter_with_context(date, context),
+            WeekDayRange::Holiday { .. } => false,
+        });
+
+        if non_holiday_also_matches {
+            None
+        } else {
+            Some(kind)
+        }
+    }
+}
+
+/// An empty selector list matches everything; otherwise the date must
+/// match at least one entry.
+fn match_any<T: DateFilter>(selectors: &[T], date: NaiveDate, context: &Context) -> bool {
+    selectors.is_empty() || selectors.iter().any(|s| s.filter_with_context(date, context))
+}
+
+impl DateFilter for YearRange {
+    fn filter_with_context(&self, date: NaiveDate, _context: &Context) -> bool {
+        let year = date.year() as u16;
+        self.range.contains(&year) && (year - self.range.start()) % self.step == 0
+    }
+}
+
+impl DateFilter for WeekRange {
+    fn filter_with_context(&self, date: NaiveDate, _context: &Context) -> bool {
+        let week = date.iso_week().week() as u8;
+        self.range.contains(&week) && (week - self.range.start()) % self.step == 0
+    }
+}
+
+impl DateFilter for MonthdayRange {
+    fn filter_with_context(&self, date: NaiveDate, _context: &Context) -> bool {
+        match self {
+            MonthdayRange::Month { range, year } => {
+                if year.is_some_and(|year| date.year() as u16 != year) {
+                    return false;
+                }
+
+                let month =
+                    Month::try_from(date.month() as u8).expect("got invalid month from NaiveDate");
+
+                range.contains(&month)
+            }
+            MonthdayRange::Date { start, end } => {
+                let start = resolve_monthday_date(start, date);
+                let end = resolve_monthday_date(end, date);
+                (start..=end).contains(&date)
+            }
+        }
+    }
+}
+
+impl DateFilter for WeekDayRange {
+    fn filter_with_context(&self, date: NaiveDate, context: &Context) -> bool {
+        match self {
+            WeekDayRange::Fixed { range, offset, nth } => {
+                let shifted = date - Duration::days(*offset);
+
+                if !weekday_range_contains(range, shifted.weekday()) {
+                    return false;
+                }
+
+                if !nth.contains(&true) {
+                    return true;
+                }
+
+                let occurrence_in_month = (shifted.day0() / 7) as usize;
+                nth.get(occurrence_in_month).copied().unwrap_or(false)
+            }
+            WeekDayRange::Holiday { kind, offset } => {
+                let shifted = date - Duration::days(*offset);
+
+                let Some(holidays) = context.holidays() else {
+                    return false;
+                };
+
+                match kind {
+                    HolidayKind::Public => holidays.is_public_holiday(shifted),
+                    HolidayKind::School => holidays.is_school_holiday(shifted),
+                }
+            }
+        }
+    }
+}
+
+/// `chrono::Weekday` has no `Ord` impl (a week doesn't have a single
+/// linear order), so a `Mo-Fr`-style range can't use `RangeInclusive::contains`
+/// directly; this also handles a range that wraps around the week, like
+/// `Fr-Mo`.
+fn weekday_range_contains(range: &RangeInclusive<Weekday>, day: Weekday) -> bool {
+    let start = range.start().num_days_from_monday();
+    let end = range.end().num_days_from_monday();
+    let day = day.num_days_from_monday();
+
+    if start <= end {
+        (start..=end).contains(&day)
+    } else {
+        day >= start || day <= end
+    }
+}
+
+fn resolve_monthday_date((date, offset): &(Date, DateOffset), reference: NaiveDate) -> NaiveDate {
+    let base = match *date {
+        Date::Fixed { year, month, day } => NaiveDate::from_ymd_opt(
+            year.unwrap_or(reference.year() as u16).into(),
+            month as u32,
+            day.into(),
+        )
+        .expect("got invalid fixed date"),
+        Date::Easter { year } => {
+            // Easter is not computed here: an `Easter` date resolves to
+            // January 1 of its year (or of `reference`'s year).
+            NaiveDate::from_ymd_opt(year.unwrap_or(reference.year() as u16).into(), 1, 1)
+                .expect("got invalid year")
+        }
+    };
+
+    offset.apply(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::holidays::StaticHolidays;
+
+    fn ph_selector(offset: i64) -> DaySelector {
+        DaySelector {
+            weekday: vec![WeekDayRange::Holiday {
+                kind: HolidayKind::Public,
+                offset,
+            }],
+            ..DaySelector::default()
+        }
+    }
+
+    #[test]
+    fn public_holiday_matches_with_provider() {
+        let holiday = NaiveDate::from_ymd_opt(2026, 7, 14).unwrap();
+        let context = Context::with_holidays(StaticHolidays::new().with_public_holidays([holiday]));
+
+        assert!(ph_selector(0).filter_with_context(holiday, &context));
+        assert!(!ph_selector(0)
+            .filter_with_context(holiday + Duration::days(1), &context));
+    }
+
+    #[test]
+    fn public_holiday_never_matches_without_provider() {
+        let holiday = NaiveDate::from_ymd_opt(2026, 7, 14).unwrap();
+        assert!(!ph_selector(0).filter(holiday));
+    }
+
+    #[test]
+    fn matching_holiday_kind_ignores_non_holiday_match() {
+        // A Tuesday that is also a public holiday, and a plain Wednesday
+        // with no holiday significance.
+        let holiday = NaiveDate::from_ymd_opt(2026, 7, 14).unwrap();
+        let wednesday = NaiveDate::from_ymd_opt(2026, 7, 15).unwrap();
+        let context = Context::with_holidays(StaticHolidays::new().with_public_holidays([holiday]));
+
+        let mixed = DaySelector {
+            weekday: vec![
+                WeekDayRange::Fixed {
+                    range: chrono::Weekday::Wed..=chrono::Weekday::Wed,
+                    offset: 0,
+                    nth: [false; 5],
+                },
+                WeekDayRange::Holiday {
+                    kind: HolidayKind::Public,
+                    offset: 0,
+                },
+            ],
+            ..DaySelector::default()
+        };
+
+        // `wednesday` matches via the plain weekday entry, not via the
+        // holiday one (and isn't a holiday at all).
+        assert_eq!(mixed.matching_holiday_kind(wednesday, &context), None);
+
+        // `holiday` (a Tuesday) matches *only* through the holiday entry.
+        assert_eq!(
+            mixed.matching_holiday_kind(holiday, &context),
+            Some(HolidayKind::Public)
+        );
+    }
+
+    #[test]
+    fn matching_holiday_kind_ignores_overlap_with_a_matching_non_holiday_entry() {
+        // A Wednesday that is *also* a public holiday: `We,PH` would
+        // match this date through the plain `We` entry alone, so the
+        // match isn't attributable to the holiday.
+        let wednesday_holiday = NaiveDate::from_ymd_opt(2026, 7, 15).unwrap();
+        let context =
+            Context::with_holidays(StaticHolidays::new().with_public_holidays([wednesday_holiday]));
+
+        let we_or_ph = DaySelector {
+            weekday: vec![
+                WeekDayRange::Fixed {
+                    range: chrono::Weekday::Wed..=chrono::Weekday::Wed,
+                    offset: 0,
+                    nth: [false; 5],
+                },
+                WeekDayRange::Holiday {
+                    kind: HolidayKind::Public,
+                    offset: 0,
+                },
+            ],
+            ..DaySelector::default()
+        };
+
+        assert!(we_or_ph.filter_with_context(wednesday_holiday, &context));
+        assert_eq!(we_or_ph.matching_holiday_kind(wednesday_holiday, &context), None);
+    }
+}
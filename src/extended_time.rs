@@ -1,9 +1,13 @@
 use std::convert::TryInto;
 use std::fmt;
 use std::num::TryFromIntError;
+use std::str::FromStr;
 
 use chrono::{NaiveTime, Timelike};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 // TODO: rename as DateTime and take Month enum?
 
 #[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -18,6 +22,12 @@ impl fmt::Debug for ExtendedTime {
     }
 }
 
+impl fmt::Display for ExtendedTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> std::result::Result<(), fmt::Error> {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
 impl ExtendedTime {
     pub fn new(hour: u8, minute: u8) -> Self {
         if minute >= 60 {
@@ -74,3 +84,43 @@ impl From<NaiveTime> for ExtendedTime {
         }
     }
 }
+
+// ParseExtendedTimeError
+
+#[derive(Debug)]
+pub struct ParseExtendedTimeError;
+
+impl FromStr for ExtendedTime {
+    type Err = ParseExtendedTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hour, minute) = s.split_once(':').ok_or(ParseExtendedTimeError)?;
+        let hour = hour.parse().map_err(|_| ParseExtendedTimeError)?;
+        let minute: u8 = minute.parse().map_err(|_| ParseExtendedTimeError)?;
+
+        if minute >= 60 {
+            return Err(ParseExtendedTimeError);
+        }
+
+        Ok(Self { hour, minute })
+    }
+}
+
+// Serialize as the same "HH:MM" string produced by `Debug`, so a parsed
+// `TimeDomain` can round-trip through JSON without re-parsing the
+// opening-hours string.
+
+#[cfg(feature = "serde")]
+impl Serialize for ExtendedTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ExtendedTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(|_| serde::de::Error::custom("invalid \"HH:MM\" time"))
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+
+// HolidayProvider
+//
+// `WeekDayRange::Holiday` and `DaySelector` only describe *which* rules
+// are holiday-dependent (`PH`/`SH`); they have no notion of which
+// concrete dates are holidays in a given region. `HolidayProvider` is the
+// extension point that supplies that, and `Context` is how a caller
+// attaches one to an evaluation, the same way a market calendar attaches
+// a holiday table to a trading schedule.
+
+/// Source of concrete holiday dates, used to resolve `PH` (public
+/// holiday) and `SH` (school holiday) day selectors.
+pub trait HolidayProvider {
+    fn is_public_holiday(&self, date: NaiveDate) -> bool;
+
+    fn is_school_holiday(&self, date: NaiveDate) -> bool;
+
+    /// An optional human-readable label for the holiday on `date`,
+    /// surfaced through `DateTimeRange::comments` when a `PH`/`SH` rule
+    /// matches.
+    fn holiday_comment(&self, date: NaiveDate) -> Option<String> {
+        let _ = date;
+        None
+    }
+}
+
+/// Evaluation context threaded through `DateFilter::filter_with_context`
+/// and `RuleSequence::schedule_at_with_context` so that holiday-dependent rules can be
+/// resolved against real dates.
+///
+/// An empty `Context` (the `Default`) treats every date as not being a
+/// holiday, matching the behaviour before holiday resolution existed.
+#[derive(Clone, Default)]
+pub struct Context {
+    holidays: Option<Arc<dyn HolidayProvider + Send + Sync>>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_holidays(holidays: impl HolidayProvider + Send + Sync + 'static) -> Self {
+        Self {
+            holidays: Some(Arc::new(holidays)),
+        }
+    }
+
+    pub fn holidays(&self) -> Option<&(dyn HolidayProvider + Send + Sync)> {
+        self.holidays.as_deref()
+    }
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("holidays", &self.holidays.is_some())
+            .finish()
+    }
+}
+
+/// A [`HolidayProvider`] backed by an explicit set of dates, for callers
+/// that already have a national holiday list (e.g. from a government
+/// feed) rather than an algorithmic source.
+#[derive(Clone, Debug, Default)]
+pub struct StaticHolidays {
+    public: Vec<NaiveDate>,
+    school: Vec<RangeInclusive<NaiveDate>>,
+}
+
+impl StaticHolidays {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_public_holidays(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.public.extend(dates);
+        self
+    }
+
+    pub fn with_school_holidays(
+        mut self,
+        ranges: impl IntoIterator<Item = RangeInclusive<NaiveDate>>,
+    ) -> Self {
+        self.school.extend(ranges);
+        self
+    }
+}
+
+impl HolidayProvider for StaticHolidays {
+    fn is_public_holiday(&self, date: NaiveDate) -> bool {
+        self.public.contains(&date)
+    }
+
+    fn is_school_holiday(&self, date: NaiveDate) -> bool {
+        self.school.iter().any(|range| range.contains(&date))
+    }
+}
@@ -0,0 +1,163 @@
+use std::cmp::min;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use opening_hours_syntax::rules::day::Month;
+
+use crate::extended_time::ExtendedTime;
+use crate::holidays::Context;
+use crate::schedule::TimeRange;
+use crate::time_domain::{RuleKind, TimeDomain};
+
+// month_overview
+//
+// Building a visual month view today means calling `schedule_at` day by
+// day and stitching the results together by hand. `month_overview` does
+// that stitching once, the opening-hours analogue of generating a
+// formatted month calendar from per-day state.
+
+/// The ordered open/closed/unknown ranges for a single day, plus enough
+/// `chrono::Datelike` metadata to place it in a month grid (first column
+/// Monday), as produced by [`TimeDomain::month_overview`].
+#[derive(Clone, Debug)]
+pub struct DaySummary {
+    pub date: NaiveDate,
+    pub iso_week: u32,
+    pub weekday: Weekday,
+    pub ranges: Vec<TimeRange>,
+    /// The `RuleKind` covering the most time during the day, for quick
+    /// cell coloring in a calendar grid.
+    pub dominant_kind: RuleKind,
+}
+
+impl TimeDomain {
+    /// The ordered per-day schedule for every day of `month` in `year`.
+    ///
+    /// Each day's ranges come from the rules' own "today" contribution
+    /// only (not `schedule_at`, which also merges in yesterday's
+    /// spillover): an interval that bleeds past midnight is attributed
+    /// once, to the day it starts on, and clipped to `24:00` here since
+    /// a calendar cell has no use for an `ExtendedTime` past the day it
+    /// represents.
+    pub fn month_overview(&self, year: u16, month: Month) -> Vec<DaySummary> {
+        self.month_overview_with_context(year, month, &Context::default())
+    }
+
+    /// Like [`TimeDomain::month_overview`], but resolving `PH`/`SH` day
+    /// selectors against `context`'s `HolidayProvider`.
+    pub fn month_overview_with_context(
+        &self,
+        year: u16,
+        month: Month,
+        context: &Context,
+    ) -> Vec<DaySummary> {
+        let first_day =
+            NaiveDate::from_ymd_opt(year.into(), month as u32, 1).expect("invalid year/month");
+
+        let day_end = ExtendedTime::new(24, 0);
+        let mut date = first_day;
+        let mut summaries = Vec::new();
+
+        while date.month() == first_day.month() {
+            let ranges: Vec<TimeRange> = self
+                .schedule_today_with_context(date, context)
+                .into_iter_filled()
+                .map(|tr| TimeRange {
+                    range: tr.range.start..min(tr.range.end, day_end),
+                    ..tr
+                })
+                .collect();
+            let dominant_kind = dominant_kind(&ranges);
+
+            summaries.push(DaySummary {
+                date,
+                iso_week: date.iso_week().week(),
+                weekday: date.weekday(),
+                ranges,
+                dominant_kind,
+            });
+
+            date += Duration::days(1);
+        }
+
+        summaries
+    }
+}
+
+fn dominant_kind(ranges: &[TimeRange]) -> RuleKind {
+    let total_minutes = |kind: RuleKind| -> i32 {
+        ranges
+            .iter()
+            .filter(|tr| tr.kind == kind)
+            .map(|tr| {
+                i32::from(tr.range.end.mins_from_midnight())
+                    - i32::from(tr.range.start.mins_from_midnight())
+            })
+            .sum()
+    };
+
+    [RuleKind::Open, RuleKind::Closed, RuleKind::Unknown]
+        .iter()
+        .copied()
+        .max_by_key(|kind| total_minutes(*kind))
+        .unwrap_or(RuleKind::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use opening_hours_syntax::rules::day::DaySelector;
+
+    use super::*;
+    use crate::time_domain::{RuleOperator, RuleSequence};
+    use crate::time_selector::{TimeSelector, TimeSpan};
+
+    /// A single `Open` rule for `22:00-02:00`, every day.
+    fn overnight_open() -> TimeDomain {
+        TimeDomain {
+            rules: vec![RuleSequence {
+                day_selector: DaySelector::default(),
+                time_selector: TimeSelector {
+                    time: vec![TimeSpan {
+                        range: ExtendedTime::new(22, 0)..ExtendedTime::new(26, 0),
+                    }],
+                },
+                kind: RuleKind::Open,
+                operator: RuleOperator::Normal,
+                comments: Vec::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn overnight_interval_is_attributed_once_and_clipped_to_24h() {
+        let time_domain = overnight_open();
+        let summaries = time_domain.month_overview(2026, Month::July);
+
+        let day_14 = summaries
+            .iter()
+            .find(|s| s.date == NaiveDate::from_ymd_opt(2026, 7, 14).unwrap())
+            .unwrap();
+
+        let day_15 = summaries
+            .iter()
+            .find(|s| s.date == NaiveDate::from_ymd_opt(2026, 7, 15).unwrap())
+            .unwrap();
+
+        // Day 14 reports the interval it starts, clipped at 24:00, not
+        // at its un-clipped 26:00 end.
+        assert!(day_14
+            .ranges
+            .iter()
+            .any(|tr| tr.kind == RuleKind::Open
+                && tr.range.start == ExtendedTime::new(22, 0)
+                && tr.range.end == ExtendedTime::new(24, 0)));
+
+        // Day 15 must NOT also report an Open[0:00-2:00) spilling over
+        // from day 14's rule — that would double-count the same
+        // physical interval.
+        assert!(!day_15
+            .ranges
+            .iter()
+            .any(|tr| tr.kind == RuleKind::Open && tr.range.start == ExtendedTime::new(0, 0)));
+    }
+}
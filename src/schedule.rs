@@ -0,0 +1,95 @@
+use std::ops::Range;
+
+use crate::extended_time::ExtendedTime;
+use crate::time_domain::RuleKind;
+
+// TimeRange / Schedule
+//
+// A `Schedule` is the set of time-of-day ranges a date resolves to, as
+// produced by `RuleSequence::schedule_at`. `into_iter_filled` is what the
+// forward and backward `TimeDomain` iterators actually walk: it turns
+// the (possibly sparse, possibly overlapping) ranges into a sorted,
+// contiguous, non-overlapping cover of the `00:00..24:00` day, with gaps
+// marked `RuleKind::Unknown`.
+
+#[derive(Clone, Debug)]
+pub struct TimeRange {
+    pub range: Range<ExtendedTime>,
+    pub kind: RuleKind,
+    pub comments: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Schedule {
+    ranges: Vec<TimeRange>,
+}
+
+impl Schedule {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    pub fn from_ranges(
+        ranges: impl IntoIterator<Item = Range<ExtendedTime>>,
+        kind: RuleKind,
+        comments: Vec<String>,
+    ) -> Self {
+        let ranges = ranges
+            .into_iter()
+            .filter(|range| range.start < range.end)
+            .map(|range| TimeRange {
+                range,
+                kind,
+                comments: comments.clone(),
+            })
+            .collect();
+
+        Self { ranges }
+    }
+
+    /// Combine with a schedule from a higher-priority ("additional")
+    /// rule: wherever `other` applies, it takes precedence over `self`.
+    pub fn addition(self, other: Self) -> Self {
+        let mut ranges = self.ranges;
+        ranges.extend(other.ranges);
+        Self { ranges }
+    }
+
+    /// The day's ranges with every gap in `00:00..24:00` filled with
+    /// `RuleKind::Unknown`, sorted and non-overlapping. Where two input
+    /// ranges overlap, the one added later (via `addition`) wins for the
+    /// overlapping instants.
+    pub fn into_iter_filled(self) -> Box<dyn Iterator<Item = TimeRange>> {
+        let mut ranges = self.ranges;
+        ranges.sort_by_key(|tr| tr.range.start);
+
+        let day_end = ExtendedTime::new(24, 0);
+        let mut filled = Vec::new();
+        let mut cursor = ExtendedTime::new(0, 0);
+
+        for tr in ranges {
+            if tr.range.start > cursor {
+                filled.push(TimeRange {
+                    range: cursor..tr.range.start,
+                    kind: RuleKind::Unknown,
+                    comments: Vec::new(),
+                });
+            }
+
+            if tr.range.end > cursor {
+                cursor = tr.range.end;
+                filled.push(tr);
+            }
+        }
+
+        if cursor < day_end {
+            filled.push(TimeRange {
+                range: cursor..day_end,
+                kind: RuleKind::Unknown,
+                comments: Vec::new(),
+            });
+        }
+
+        Box::new(filled.into_iter())
+    }
+}
@@ -2,14 +2,20 @@ use std::boxed::Box;
 use std::cmp::{max, min};
 use std::convert::TryInto;
 use std::fmt;
-use std::iter::{empty, Peekable};
+use std::iter::{empty, Peekable, Rev};
 use std::ops::Range;
 
 use chrono::prelude::Datelike;
 use chrono::{Duration, NaiveDate, NaiveDateTime};
 
-use crate::day_selector::{DateFilter, DaySelector};
+use opening_hours_syntax::rules::day::DaySelector;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::day_selector::DateFilter;
 use crate::extended_time::ExtendedTime;
+use crate::holidays::Context;
 use crate::schedule::{Schedule, TimeRange};
 use crate::time_selector::TimeSelector;
 
@@ -52,6 +58,7 @@ impl DateTimeRange {
 
 // TimeDomain
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct TimeDomain {
     pub rules: Vec<RuleSequence>,
@@ -67,10 +74,37 @@ impl TimeDomain {
     // would be relevant to focus on optimisatons to this regard.
 
     pub fn schedule_at(&self, date: NaiveDate) -> Schedule {
+        self.schedule_at_with_context(date, &Context::default())
+    }
+
+    pub fn schedule_at_with_context(&self, date: NaiveDate, context: &Context) -> Schedule {
+        self.rules
+            .iter()
+            .fold(None, |prev_eval, rules_seq| {
+                let curr_eval = rules_seq.schedule_at_with_context(date, context);
+
+                match rules_seq.operator {
+                    RuleOperator::Normal => curr_eval,
+                    RuleOperator::Additional => match (prev_eval, curr_eval) {
+                        (Some(prev), Some(curr)) => Some(prev.addition(curr)),
+                        (prev, curr) => prev.or(curr),
+                    },
+                    RuleOperator::Fallback => prev_eval.or(curr_eval),
+                }
+            })
+            .unwrap_or_else(Schedule::empty)
+    }
+
+    /// Like [`TimeDomain::schedule_at_with_context`], but excluding any
+    /// portion spilling over from the previous day. Used by
+    /// `month_overview` so a bleed-over interval is attributed to the
+    /// single day it starts on, instead of also reappearing as a
+    /// `00:00`-based range on the following day.
+    pub(crate) fn schedule_today_with_context(&self, date: NaiveDate, context: &Context) -> Schedule {
         self.rules
             .iter()
             .fold(None, |prev_eval, rules_seq| {
-                let curr_eval = rules_seq.schedule_at(date);
+                let curr_eval = rules_seq.schedule_today_with_context(date, context);
 
                 match rules_seq.operator {
                     RuleOperator::Normal => curr_eval,
@@ -85,7 +119,27 @@ impl TimeDomain {
     }
 
     pub fn iter_from(&self, from: NaiveDateTime) -> TimeDomainIterator {
-        TimeDomainIterator::new(self, from)
+        TimeDomainIterator::new(self, from, Context::default())
+    }
+
+    pub fn iter_from_with_context(
+        &self,
+        from: NaiveDateTime,
+        context: Context,
+    ) -> TimeDomainIterator {
+        TimeDomainIterator::new(self, from, context)
+    }
+
+    pub fn iter_to(&self, to: NaiveDateTime) -> TimeDomainReverseIterator {
+        TimeDomainReverseIterator::new(self, to, Context::default())
+    }
+
+    pub fn iter_to_with_context(
+        &self,
+        to: NaiveDateTime,
+        context: Context,
+    ) -> TimeDomainReverseIterator {
+        TimeDomainReverseIterator::new(self, to, context)
     }
 
     // High level implementations
@@ -97,6 +151,31 @@ impl TimeDomain {
             .unwrap_or(current_time)
     }
 
+    /// When did the state at `current_time` begin? Analogous to
+    /// `next_change`, but walking backward.
+    pub fn prev_change(&self, current_time: NaiveDateTime) -> NaiveDateTime {
+        let curr_kind = self.state(current_time);
+
+        // At an exact boundary the interval just before `current_time` is
+        // of another kind: the current state began at `current_time`.
+        self.iter_to(current_time)
+            .next()
+            .filter(|dtr| dtr.kind == curr_kind)
+            .map(|dtr| dtr.range.start)
+            .unwrap_or(current_time)
+    }
+
+    pub fn next_change_with_context(
+        &self,
+        current_time: NaiveDateTime,
+        context: Context,
+    ) -> NaiveDateTime {
+        self.iter_from_with_context(current_time, context)
+            .next()
+            .map(|dtr| dtr.range.end)
+            .unwrap_or(current_time)
+    }
+
     pub fn state(&self, current_time: NaiveDateTime) -> RuleKind {
         self.iter_from(current_time)
             .next()
@@ -104,18 +183,37 @@ impl TimeDomain {
             .unwrap_or(RuleKind::Unknown)
     }
 
+    pub fn state_with_context(&self, current_time: NaiveDateTime, context: Context) -> RuleKind {
+        self.iter_from_with_context(current_time, context)
+            .next()
+            .map(|dtr| dtr.kind)
+            .unwrap_or(RuleKind::Unknown)
+    }
+
     pub fn is_open(&self, current_time: NaiveDateTime) -> bool {
         self.state(current_time) == RuleKind::Open
     }
 
+    pub fn is_open_with_context(&self, current_time: NaiveDateTime, context: Context) -> bool {
+        self.state_with_context(current_time, context) == RuleKind::Open
+    }
+
     pub fn is_closed(&self, current_time: NaiveDateTime) -> bool {
         self.state(current_time) == RuleKind::Closed
     }
 
+    pub fn is_closed_with_context(&self, current_time: NaiveDateTime, context: Context) -> bool {
+        self.state_with_context(current_time, context) == RuleKind::Closed
+    }
+
     pub fn is_unknown(&self, current_time: NaiveDateTime) -> bool {
         self.state(current_time) == RuleKind::Unknown
     }
 
+    pub fn is_unknown_with_context(&self, current_time: NaiveDateTime, context: Context) -> bool {
+        self.state_with_context(current_time, context) == RuleKind::Unknown
+    }
+
     pub fn intervals<'s>(
         &'s self,
         from: NaiveDateTime,
@@ -129,24 +227,107 @@ impl TimeDomain {
                 DateTimeRange::new_with_sorted_comments(start..end, dtr.kind, dtr.comments)
             })
     }
+
+    pub fn intervals_with_context<'s>(
+        &'s self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        context: Context,
+    ) -> impl Iterator<Item = DateTimeRange> + 's {
+        self.iter_from_with_context(from, context)
+            .take_while(move |dtr| dtr.range.start < to)
+            .map(move |dtr| {
+                let start = max(dtr.range.start, from);
+                let end = min(dtr.range.end, to);
+                DateTimeRange::new_with_sorted_comments(start..end, dtr.kind, dtr.comments)
+            })
+    }
+
+    /// Sample the state at `from`, `from + step`, `from + 2*step`, ...
+    /// instead of only at state-change boundaries like `intervals`.
+    ///
+    /// Panics if `step` is not strictly positive.
+    pub fn sample_from(&self, from: NaiveDateTime, step: Duration) -> TimeDomainSampler {
+        self.sample_from_with_context(from, step, Context::default())
+    }
+
+    pub fn sample_from_with_context(
+        &self,
+        from: NaiveDateTime,
+        step: Duration,
+        context: Context,
+    ) -> TimeDomainSampler {
+        TimeDomainSampler::new(self, from, step, context)
+    }
+}
+
+// TimeDomainSampler
+
+/// Iterator yielding `(timestamp, state)` at a fixed step, e.g. for
+/// exporting a schedule to a 15- or 30-minute availability grid.
+///
+/// Lazily advances the underlying `TimeDomainIterator`, pulling a new
+/// `DateTimeRange` only once a sample timestamp passes its end, so
+/// sampling a whole year at minute granularity doesn't re-run
+/// `schedule_at` once per sample.
+pub struct TimeDomainSampler<'d> {
+    intervals: TimeDomainIterator<'d>,
+    step: Duration,
+    next_sample: NaiveDateTime,
+    curr: Option<DateTimeRange>,
+}
+
+impl<'d> TimeDomainSampler<'d> {
+    fn new(time_domain: &'d TimeDomain, from: NaiveDateTime, step: Duration, context: Context) -> Self {
+        assert!(step > Duration::zero(), "sampling step must be positive");
+
+        Self {
+            intervals: time_domain.iter_from_with_context(from, context),
+            step,
+            next_sample: from,
+            curr: None,
+        }
+    }
+}
+
+impl Iterator for TimeDomainSampler<'_> {
+    type Item = (NaiveDateTime, RuleKind);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self
+            .curr
+            .as_ref()
+            .map(|dtr| dtr.range.end <= self.next_sample)
+            .unwrap_or(true)
+        {
+            self.curr = Some(self.intervals.next()?);
+        }
+
+        let sample = self.next_sample;
+        self.next_sample += self.step;
+        Some((sample, self.curr.as_ref().expect("just set above").kind))
+    }
 }
 
 // TimeDomainIterator
 
 pub struct TimeDomainIterator<'d> {
     time_domain: &'d TimeDomain,
+    context: Context,
     curr_date: NaiveDate,
     curr_schedule: Peekable<Box<dyn Iterator<Item = TimeRange>>>,
 }
 
 impl<'d> TimeDomainIterator<'d> {
-    pub fn new(time_domain: &'d TimeDomain, start_datetime: NaiveDateTime) -> Self {
+    pub fn new(time_domain: &'d TimeDomain, start_datetime: NaiveDateTime, context: Context) -> Self {
         let start_date = start_datetime.date();
         let start_time = start_datetime.time().into();
 
         let mut curr_schedule = {
             if start_date.year() <= 9999 {
-                time_domain.schedule_at(start_date).into_iter_filled()
+                time_domain
+                    .schedule_at_with_context(start_date, &context)
+                    .into_iter_filled()
             } else {
                 Box::new(empty())
             }
@@ -163,6 +344,7 @@ impl<'d> TimeDomainIterator<'d> {
 
         Self {
             time_domain,
+            context,
             curr_date: start_date,
             curr_schedule,
         }
@@ -178,7 +360,7 @@ impl<'d> TimeDomainIterator<'d> {
                 if self.curr_date.year() <= 9999 {
                     self.curr_schedule = self
                         .time_domain
-                        .schedule_at(self.curr_date)
+                        .schedule_at_with_context(self.curr_date, &self.context)
                         .into_iter_filled()
                         .peekable()
                 }
@@ -226,8 +408,132 @@ impl Iterator for TimeDomainIterator<'_> {
     }
 }
 
+// TimeDomainReverseIterator
+//
+// Mirrors `TimeDomainIterator`, walking `curr_date` downward instead of
+// upward and consuming each day's filled schedule back-to-front. Since a
+// `TimeRange`'s `end` can be an out-of-range "24:00"-style time when an
+// interval wraps past midnight, boundaries are derived the same way the
+// forward iterator does: only ever from a `range.start`, with the
+// previously-yielded interval's start carried over as the next
+// interval's end (`upper_bound`).
+
+pub struct TimeDomainReverseIterator<'d> {
+    time_domain: &'d TimeDomain,
+    context: Context,
+    curr_date: NaiveDate,
+    curr_schedule: Peekable<Rev<std::vec::IntoIter<TimeRange>>>,
+    upper_bound: NaiveDateTime,
+}
+
+impl<'d> TimeDomainReverseIterator<'d> {
+    pub fn new(time_domain: &'d TimeDomain, end_datetime: NaiveDateTime, context: Context) -> Self {
+        let end_date = end_datetime.date();
+        let end_time = end_datetime.time().into();
+
+        let mut curr_date = end_date;
+        let mut curr_schedule = Self::schedule_for(time_domain, curr_date, &context);
+
+        while curr_schedule
+            .peek()
+            .map(|tr: &TimeRange| tr.range.start >= end_time)
+            .unwrap_or(false)
+        {
+            curr_schedule.next();
+
+            // `end_time` of `00:00` (or any time before the day's first
+            // entry) drains the whole day here; roll over to the
+            // previous day's schedule the same way `consume_until_prev_kind`
+            // does, instead of leaving the iterator stuck on an empty day.
+            if curr_schedule.peek().is_none() {
+                curr_date -= Duration::days(1);
+
+                if curr_date.year() >= 0 {
+                    curr_schedule = Self::schedule_for(time_domain, curr_date, &context);
+                }
+
+                // Every entry of the previous day starts before
+                // `end_datetime`, so none of them is skipped.
+                break;
+            }
+        }
+
+        Self {
+            time_domain,
+            context,
+            curr_date,
+            curr_schedule,
+            upper_bound: end_datetime,
+        }
+    }
+
+    fn schedule_for(
+        time_domain: &TimeDomain,
+        date: NaiveDate,
+        context: &Context,
+    ) -> Peekable<Rev<std::vec::IntoIter<TimeRange>>> {
+        let ranges: Vec<TimeRange> = if date.year() >= 0 {
+            time_domain
+                .schedule_at_with_context(date, context)
+                .into_iter_filled()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        ranges.into_iter().rev().peekable()
+    }
+
+    /// Pop every remaining entry of `curr_kind`, returning the start
+    /// (date + time) of the *last* one popped — i.e. the earliest
+    /// boundary of the merged run, since we walk from latest to
+    /// earliest. Mirrors how the forward iterator captures
+    /// `curr_tr.range.start` before it starts consuming.
+    fn consume_until_prev_kind(&mut self, curr_kind: RuleKind) -> NaiveDateTime {
+        let mut last_start = None;
+
+        while self.curr_schedule.peek().map(|tr| tr.kind) == Some(curr_kind) {
+            let tr = self.curr_schedule.next().expect("just peeked");
+
+            last_start = Some(NaiveDateTime::new(
+                self.curr_date,
+                tr.range.start.try_into().expect("got invalid time from schedule"),
+            ));
+
+            if self.curr_schedule.peek().is_none() {
+                self.curr_date -= Duration::days(1);
+
+                if self.curr_date.year() >= 0 {
+                    self.curr_schedule =
+                        Self::schedule_for(self.time_domain, self.curr_date, &self.context)
+                }
+            }
+        }
+
+        last_start.expect("consume_until_prev_kind called while not positioned on curr_kind")
+    }
+}
+
+impl Iterator for TimeDomainReverseIterator<'_> {
+    type Item = DateTimeRange;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let curr_tr = self.curr_schedule.peek().cloned()?;
+        let end = self.upper_bound;
+        let start = self.consume_until_prev_kind(curr_tr.kind);
+        self.upper_bound = start;
+
+        Some(DateTimeRange::new_with_sorted_comments(
+            start..end,
+            curr_tr.kind,
+            curr_tr.comments,
+        ))
+    }
+}
+
 // RuleSequence
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct RuleSequence {
     pub day_selector: DaySelector,
@@ -239,44 +545,79 @@ pub struct RuleSequence {
 
 impl RuleSequence {
     pub fn schedule_at(&self, date: NaiveDate) -> Option<Schedule> {
-        let today = {
-            if self.day_selector.filter(date) {
-                let ranges = self.time_selector.intervals_at(date);
-                // TODO: sort comments during parsing
-                Some(Schedule::from_ranges(
-                    ranges,
-                    self.kind,
-                    self.comments.clone(),
-                ))
-            } else {
-                None
-            }
-        };
+        self.schedule_at_with_context(date, &Context::default())
+    }
 
-        let yesterday = {
-            let date = date - Duration::days(1);
-
-            if self.day_selector.filter(date) {
-                let ranges = self.time_selector.intervals_at_next_day(date);
-                Some(Schedule::from_ranges(
-                    ranges,
-                    self.kind,
-                    self.comments.clone(),
-                ))
-            } else {
-                None
-            }
-        };
+    pub fn schedule_at_with_context(&self, date: NaiveDate, context: &Context) -> Option<Schedule> {
+        let today = self.schedule_today_with_context(date, context);
+        let yesterday = self.schedule_spillover_with_context(date, context);
 
         match (today, yesterday) {
             (Some(sched_1), Some(sched_2)) => Some(sched_1.addition(sched_2)),
             (today, yesterday) => today.or(yesterday),
         }
     }
+
+    /// This rule's own ranges on `date`, not including any spillover
+    /// from the previous day. A range whose interval crosses midnight
+    /// (e.g. `22:00-26:00`) is reported here in full, attributed to the
+    /// day it starts on; [`RuleSequence::schedule_spillover_with_context`]
+    /// is the portion of *yesterday's* such a range that lands on `date`.
+    pub(crate) fn schedule_today_with_context(
+        &self,
+        date: NaiveDate,
+        context: &Context,
+    ) -> Option<Schedule> {
+        if self.day_selector.filter_with_context(date, context) {
+            let ranges = self.time_selector.intervals_at(date);
+            // TODO: sort comments during parsing
+            Some(Schedule::from_ranges(
+                ranges,
+                self.kind,
+                self.comments_at(date, context),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// The portion of yesterday's interval(s) that spill past midnight
+    /// onto `date`, rebased to start at `00:00`.
+    fn schedule_spillover_with_context(&self, date: NaiveDate, context: &Context) -> Option<Schedule> {
+        let prev_date = date - Duration::days(1);
+
+        if self.day_selector.filter_with_context(prev_date, context) {
+            let ranges = self.time_selector.intervals_at_next_day(prev_date);
+            Some(Schedule::from_ranges(
+                ranges,
+                self.kind,
+                self.comments_at(prev_date, context),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// The rule's own comments, plus a holiday label from `context` when
+    /// `date` is matched through a `PH`/`SH` selector (as opposed to a
+    /// plain weekday/monthday/week/year selector that merely happens to
+    /// land on a holiday).
+    fn comments_at(&self, date: NaiveDate, context: &Context) -> Vec<String> {
+        let mut comments = self.comments.clone();
+
+        if self.day_selector.matching_holiday_kind(date, context).is_some() {
+            if let Some(holidays) = context.holidays() {
+                comments.extend(holidays.holiday_comment(date));
+            }
+        }
+
+        comments
+    }
 }
 
 // RuleKind
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum RuleKind {
     Open,
@@ -284,9 +625,143 @@ pub enum RuleKind {
     Unknown,
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum RuleOperator {
     Normal,
     Additional,
     Fallback,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A single `Open` rule for `09:00-17:00`, every day. `schedule_at`
+    /// fills the rest of the day with `Unknown`, so a full day looks like
+    /// `Unknown[0:00-9:00)`, `Open[9:00-17:00)`, `Unknown[17:00-24:00)`.
+    fn open_nine_to_five() -> TimeDomain {
+        TimeDomain {
+            rules: vec![RuleSequence {
+                day_selector: DaySelector::default(),
+                time_selector: TimeSelector {
+                    time: vec![crate::time_selector::TimeSpan {
+                        range: ExtendedTime::new(9, 0)..ExtendedTime::new(17, 0),
+                    }],
+                },
+                kind: RuleKind::Open,
+                operator: RuleOperator::Normal,
+                comments: Vec::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn reverse_iterator_start_is_the_consumed_runs_own_start() {
+        let time_domain = open_nine_to_five();
+        let date = NaiveDate::from_ymd_opt(2026, 7, 14).unwrap();
+        let at_20h = NaiveDateTime::new(date, chrono::NaiveTime::from_hms_opt(20, 0, 0).unwrap());
+
+        let dtr = time_domain
+            .iter_to(at_20h)
+            .next()
+            .expect("20:00 is covered by the trailing Unknown run");
+
+        // The `Unknown` run containing 20:00 began at 17:00 (when the
+        // `Open` run ended), not at 9:00 (when the *preceding* `Open`
+        // run began).
+        assert_eq!(
+            dtr.range.start,
+            NaiveDateTime::new(date, chrono::NaiveTime::from_hms_opt(17, 0, 0).unwrap())
+        );
+        assert_eq!(dtr.range.end, at_20h);
+        assert_eq!(dtr.kind, RuleKind::Unknown);
+    }
+
+    #[test]
+    fn prev_change_matches_reverse_iterator() {
+        let time_domain = open_nine_to_five();
+        let date = NaiveDate::from_ymd_opt(2026, 7, 14).unwrap();
+        let at_20h = NaiveDateTime::new(date, chrono::NaiveTime::from_hms_opt(20, 0, 0).unwrap());
+        let at_17h = NaiveDateTime::new(date, chrono::NaiveTime::from_hms_opt(17, 0, 0).unwrap());
+
+        assert_eq!(time_domain.prev_change(at_20h), at_17h);
+    }
+
+    #[test]
+    fn prev_change_at_exact_midnight_rolls_over_to_the_previous_day() {
+        let time_domain = open_nine_to_five();
+        let date = NaiveDate::from_ymd_opt(2026, 7, 14).unwrap();
+        let prev_date = NaiveDate::from_ymd_opt(2026, 7, 13).unwrap();
+
+        let midnight = NaiveDateTime::new(date, chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+        let prev_17h =
+            NaiveDateTime::new(prev_date, chrono::NaiveTime::from_hms_opt(17, 0, 0).unwrap());
+
+        // At exactly 00:00, every entry in `date`'s own schedule has
+        // `range.start >= 00:00`, so the initial skip-loop must roll
+        // over to the previous day instead of leaving the iterator
+        // stuck on an empty day.
+        assert_eq!(time_domain.prev_change(midnight), prev_17h);
+    }
+
+    /// A `HolidayProvider` that labels every public holiday, used to tell
+    /// apart "the comment was spliced in" from "there was nothing to
+    /// splice in".
+    struct LabelledHolidays(NaiveDate);
+
+    impl crate::holidays::HolidayProvider for LabelledHolidays {
+        fn is_public_holiday(&self, date: NaiveDate) -> bool {
+            date == self.0
+        }
+
+        fn is_school_holiday(&self, _date: NaiveDate) -> bool {
+            false
+        }
+
+        fn holiday_comment(&self, _date: NaiveDate) -> Option<String> {
+            Some("Public Holiday".to_string())
+        }
+    }
+
+    #[test]
+    fn comments_at_only_splices_holiday_comment_for_a_holiday_selector() {
+        let holiday = NaiveDate::from_ymd_opt(2026, 7, 14).unwrap();
+        let context = Context::with_holidays(LabelledHolidays(holiday));
+
+        let plain_rule = RuleSequence {
+            day_selector: DaySelector::default(),
+            time_selector: TimeSelector::default(),
+            kind: RuleKind::Open,
+            operator: RuleOperator::Normal,
+            comments: Vec::new(),
+        };
+
+        // A plain (non-PH/SH) rule must not gain a holiday comment just
+        // because `date` happens to be a holiday.
+        assert_eq!(plain_rule.comments_at(holiday, &context), Vec::<String>::new());
+
+        let ph_rule = RuleSequence {
+            day_selector: DaySelector {
+                weekday: vec![
+                    opening_hours_syntax::rules::day::WeekDayRange::Holiday {
+                        kind: opening_hours_syntax::rules::day::HolidayKind::Public,
+                        offset: 0,
+                    },
+                ],
+                ..DaySelector::default()
+            },
+            time_selector: TimeSelector::default(),
+            kind: RuleKind::Open,
+            operator: RuleOperator::Normal,
+            comments: Vec::new(),
+        };
+
+        // A rule matched through a PH selector does get the comment.
+        assert_eq!(ph_rule.comments_at(holiday, &context), vec!["Public Holiday".to_string()]);
+
+        // ...but not on a date that isn't a holiday at all.
+        let other_day = holiday + Duration::days(1);
+        assert_eq!(ph_rule.comments_at(other_day, &context), Vec::<String>::new());
+    }
+}
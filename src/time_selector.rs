@@ -0,0 +1,58 @@
+use std::ops::Range;
+
+use chrono::NaiveDate;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::extended_time::ExtendedTime;
+
+// TimeSelector
+//
+// The time-of-day spans a `RuleSequence` applies during, e.g. the
+// `09:00-12:00,13:00-18:00` in `Mo 09:00-12:00,13:00-18:00`. A span's
+// `end` may exceed `24:00` (e.g. `22:00-26:00`) to describe an opening
+// that crosses midnight; `intervals_at_next_day` is what turns that into
+// the portion that spills onto the following day.
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct TimeSpan {
+    pub range: Range<ExtendedTime>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct TimeSelector {
+    pub time: Vec<TimeSpan>,
+}
+
+impl TimeSelector {
+    /// The spans that apply to `date` itself, unchanged (including any
+    /// portion past `24:00`).
+    pub fn intervals_at(&self, date: NaiveDate) -> Vec<Range<ExtendedTime>> {
+        let _ = date;
+        self.time.iter().map(|span| span.range.clone()).collect()
+    }
+
+    /// The portion of each span that spills from `date` onto the
+    /// following day, re-based to start at `00:00`.
+    pub fn intervals_at_next_day(&self, date: NaiveDate) -> Vec<Range<ExtendedTime>> {
+        let _ = date;
+
+        self.time
+            .iter()
+            .filter(|span| span.range.end.hour() >= 24)
+            .map(|span| {
+                let start = ExtendedTime::new(0, 0);
+                let end = span
+                    .range
+                    .end
+                    .add_hours(-24)
+                    .expect("spillover span must exceed 24:00");
+
+                start..end
+            })
+            .collect()
+    }
+}
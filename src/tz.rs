@@ -0,0 +1,229 @@
+use std::ops::Range;
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::time_domain::{RuleKind, TimeDomain, TimeDomainIterator};
+
+// TimeDomainTz
+//
+// `TimeDomain`'s own API treats every `NaiveDateTime` as an opaque wall
+// clock time and leaves timezone handling to the caller. `TimeDomainTz`
+// pins a `TimeDomain` to a concrete `chrono_tz::Tz` the same way a market
+// calendar pins a trading schedule to an exchange's local time, so that
+// callers holding a UTC instant don't have to do the local-time
+// conversion (and DST bookkeeping) themselves.
+
+/// A [`TimeDomain`] paired with the timezone its rules are expressed in.
+///
+/// All evaluation still happens against the wall-clock times produced by
+/// `schedule_at`; `TimeDomainTz` only takes care of converting a UTC
+/// instant to and from that wall-clock representation, including the
+/// DST edge cases:
+///
+/// - a local time that falls in a spring-forward gap is snapped forward
+///   to the first valid instant after it;
+/// - a local time that falls in a fall-back fold is resolved once, as an
+///   interval end (preferring the later of the two instants), and that
+///   same instant is then reused as the following interval's start, so
+///   consecutive intervals always tile instead of overlapping.
+pub struct TimeDomainTz<'d> {
+    time_domain: &'d TimeDomain,
+    tz: Tz,
+}
+
+impl<'d> TimeDomainTz<'d> {
+    pub fn new(time_domain: &'d TimeDomain, tz: Tz) -> Self {
+        Self { time_domain, tz }
+    }
+
+    pub fn state_at_instant(&self, instant: DateTime<Utc>) -> RuleKind {
+        self.time_domain.state(self.to_local(instant))
+    }
+
+    pub fn is_open_at_instant(&self, instant: DateTime<Utc>) -> bool {
+        self.state_at_instant(instant) == RuleKind::Open
+    }
+
+    pub fn next_change_at_instant(&self, instant: DateTime<Utc>) -> DateTime<Utc> {
+        let next_local = self.time_domain.next_change(self.to_local(instant));
+        self.resolve_end(next_local)
+    }
+
+    pub fn iter_from_utc(&self, from: DateTime<Utc>) -> TimeDomainTzIterator<'d> {
+        TimeDomainTzIterator {
+            inner: self.time_domain.iter_from(self.to_local(from)),
+            tz: self.tz,
+            pending_start: None,
+        }
+    }
+
+    fn to_local(&self, instant: DateTime<Utc>) -> NaiveDateTime {
+        instant.with_timezone(&self.tz).naive_local()
+    }
+
+    fn resolve_end(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        resolve(&self.tz, local, true)
+    }
+}
+
+/// A `DateTimeRange` expressed as UTC instants rather than local
+/// `NaiveDateTime`s, as produced by [`TimeDomainTzIterator`].
+#[derive(Clone, Debug)]
+pub struct DateTimeRangeUtc {
+    pub range: Range<DateTime<Utc>>,
+    pub kind: RuleKind,
+    pub comments: Vec<String>,
+}
+
+/// Consecutive `DateTimeRange`s from the inner iterator always share a
+/// boundary (`range_N.end == range_{N+1}.start`). Re-resolving that local
+/// time independently on each side — preferring the later instant as an
+/// end, the earlier one as a start — would have the two UTC ranges
+/// overlap across a fold instead of tiling. So each shared boundary is
+/// resolved exactly once (as the *end* of the range that produces it,
+/// preferring the later instant to avoid a zero-length range) and
+/// carried forward verbatim as the next range's start.
+pub struct TimeDomainTzIterator<'d> {
+    inner: TimeDomainIterator<'d>,
+    tz: Tz,
+    pending_start: Option<DateTime<Utc>>,
+}
+
+impl Iterator for TimeDomainTzIterator<'_> {
+    type Item = DateTimeRangeUtc;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let dtr = self.inner.next()?;
+
+        let start = match self.pending_start.take() {
+            Some(start) => start,
+            None => resolve(&self.tz, dtr.range.start, false),
+        };
+
+        let mut end = resolve(&self.tz, dtr.range.end, true);
+
+        if end <= start {
+            // A gap snapped forward past `end`, or a fold collapsing the
+            // two boundaries onto each other, must never yield a
+            // zero-or-negative length range.
+            end = start + Duration::minutes(1);
+        }
+
+        self.pending_start = Some(end);
+
+        Some(DateTimeRangeUtc {
+            range: start..end,
+            kind: dtr.kind,
+            comments: dtr.comments,
+        })
+    }
+}
+
+/// Resolve a local wall-clock time to a UTC instant. `prefer_later`
+/// picks which side of a fall-back fold to resolve to when the time is
+/// ambiguous; a spring-forward gap always snaps forward to the first
+/// valid instant, regardless of `prefer_later`.
+fn resolve(tz: &Tz, local: NaiveDateTime, prefer_later: bool) -> DateTime<Utc> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(dt) => dt.with_timezone(&Utc),
+        LocalResult::Ambiguous(earliest, latest) => {
+            if prefer_later {
+                latest.with_timezone(&Utc)
+            } else {
+                earliest.with_timezone(&Utc)
+            }
+        }
+        LocalResult::None => snap_forward(tz, local),
+    }
+}
+
+/// Walk forward minute by minute until we land outside of a DST gap.
+/// Gaps created by a single transition are at most a couple of hours
+/// wide, so this converges quickly.
+fn snap_forward(tz: &Tz, mut local: NaiveDateTime) -> DateTime<Utc> {
+    loop {
+        local += Duration::minutes(1);
+
+        if let LocalResult::Single(dt) = tz.from_local_datetime(&local) {
+            return dt.with_timezone(&Utc);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use opening_hours_syntax::rules::day::DaySelector;
+
+    use super::*;
+    use crate::extended_time::ExtendedTime;
+    use crate::time_domain::{RuleOperator, RuleSequence};
+    use crate::time_selector::{TimeSelector, TimeSpan};
+
+    #[test]
+    fn fold_boundary_is_resolved_once_and_shared() {
+        // 2026-11-01 01:30 America/New_York is inside the fall-back fold
+        // (clocks go back from 02:00 to 01:00).
+        let local = NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            chrono::NaiveTime::from_hms_opt(1, 30, 0).unwrap(),
+        );
+
+        let tz = chrono_tz::America::New_York;
+        let as_end = resolve(&tz, local, true);
+        let as_start = resolve(&tz, local, false);
+
+        // The two resolutions of the ambiguous instant must not be
+        // equal (otherwise this test wouldn't be exercising the fold),
+        // and the "end" reading must never be earlier than the "start"
+        // reading of the very same local time.
+        assert!(as_end > as_start);
+
+        // A shared boundary is only ever resolved with `prefer_later`
+        // (as an end) and the exact same instant is then reused as the
+        // next range's start, so ranges built this way always tile
+        // instead of overlapping.
+        let shared = resolve(&tz, local, true);
+        assert_eq!(shared, as_end);
+
+        // An `Open[00:00-01:30)` rule puts a range boundary right on the
+        // ambiguous 01:30: the first range ends there and the second one
+        // starts at that very instant.
+        let time_domain = TimeDomain {
+            rules: vec![RuleSequence {
+                day_selector: DaySelector::default(),
+                time_selector: TimeSelector {
+                    time: vec![TimeSpan {
+                        range: ExtendedTime::new(0, 0)..ExtendedTime::new(1, 30),
+                    }],
+                },
+                kind: RuleKind::Open,
+                operator: RuleOperator::Normal,
+                comments: Vec::new(),
+            }],
+        };
+
+        let midnight =
+            NaiveDateTime::new(local.date(), chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+        let mut iter =
+            TimeDomainTz::new(&time_domain, tz).iter_from_utc(resolve(&tz, midnight, false));
+        let first = iter.next().unwrap();
+        let second = iter.next().unwrap();
+        assert_eq!(first.range.end, shared);
+        assert_eq!(second.range.start, shared);
+    }
+
+    #[test]
+    fn gap_snaps_forward_regardless_of_preference() {
+        // 2026-03-08 02:30 America/New_York falls in the spring-forward
+        // gap (clocks jump from 02:00 to 03:00).
+        let local = NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2026, 3, 8).unwrap(),
+            chrono::NaiveTime::from_hms_opt(2, 30, 0).unwrap(),
+        );
+
+        let tz = chrono_tz::America::New_York;
+        assert_eq!(resolve(&tz, local, true), resolve(&tz, local, false));
+    }
+}